@@ -0,0 +1,70 @@
+use number::*;
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Atom {
+    Symbol(String),
+    String(String),
+    Char(char),
+    Number(Number),
+    Boolean(bool),
+    List(VecDeque<Atom>),
+}
+
+impl Atom {
+    pub fn parse(s: &str) -> Atom {
+        if let Ok(i) = s.parse::<i64>() {
+            return Atom::Number(Number::Integer(i))
+        }
+
+        if let Ok(f) = s.parse::<f64>() {
+            return Atom::Number(Number::Float(f))
+        }
+
+        match s {
+            "#t" => Atom::Boolean(true),
+            "#f" => Atom::Boolean(false),
+            _ => Atom::Symbol(s.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Atom::Symbol(ref s) => write!(f, "{}", s),
+            Atom::String(ref s) => {
+                try!(write!(f, "\""));
+                for c in s.chars() {
+                    match c {
+                        '"' => try!(write!(f, "\\\"")),
+                        '\\' => try!(write!(f, "\\\\")),
+                        _ => try!(write!(f, "{}", c)),
+                    }
+                }
+                write!(f, "\"")
+            },
+            Atom::Char(c) => {
+                match c {
+                    ' ' => write!(f, "#\\space"),
+                    '\n' => write!(f, "#\\newline"),
+                    '\t' => write!(f, "#\\tab"),
+                    _ => write!(f, "#\\{}", c),
+                }
+            },
+            Atom::Number(ref n) => write!(f, "{}", n),
+            Atom::Boolean(b) => write!(f, "{}", if b { "#t" } else { "#f" }),
+            Atom::List(ref l) => {
+                try!(write!(f, "("));
+                if let Some(first) = l.front() {
+                    try!(write!(f, "{}", first));
+                    for a in l.iter().skip(1) {
+                        try!(write!(f, " {}", a));
+                    }
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
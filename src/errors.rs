@@ -0,0 +1,19 @@
+use parser::SourcePosition;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    Parser(SourcePosition, String),
+    Eval(String),
+    EoF,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Parser(ref pos, ref msg) => write!(f, "{}: {}", pos, msg),
+            Error::Eval(ref msg) => write!(f, "{}", msg),
+            Error::EoF => write!(f, "unexpected end of input"),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use atom::*;
+use errors::*;
+use parser::*;
+use std::collections::VecDeque;
+
+/// Turns a quoted literal form into the value it denotes, without evaluating
+/// anything. Used for `'x` elements of a quasiquoted template: any `~` or
+/// `~@` under the quote is dropped rather than evaluated.
+fn quote_to_atom(node: &Node) -> Result<Atom, Error> {
+    match *node {
+        Node::Atom(ref a, _) => Ok(a.clone()),
+        Node::List(ref items, _) => {
+            let mut out: VecDeque<Atom> = VecDeque::new();
+
+            for item in items {
+                out.push_back(try!(syntax_node_to_atom(item)));
+            }
+
+            Ok(Atom::List(out))
+        }
+    }
+}
+
+fn syntax_node_to_atom(node: &SyntaxNode) -> Result<Atom, Error> {
+    match *node {
+        SyntaxNode::Node(ref n, _) => quote_to_atom(n),
+        SyntaxNode::Quote(ref n, _) => quote_to_atom(n),
+        SyntaxNode::QuasiQuote(ref n, _) => quote_to_atom(n),
+        SyntaxNode::Unquote(ref n, _) => quote_to_atom(n),
+        SyntaxNode::Splice(ref n, _) => quote_to_atom(n),
+    }
+}
+
+/// Expands a quasiquoted template into the runtime value it denotes.
+///
+/// Ordinary elements are quoted verbatim, an `~`-unquoted element is
+/// evaluated with `eval` and its value inserted, and a `~@`-spliced element
+/// must evaluate to a list whose elements are spliced into the surrounding
+/// list. `depth` starts at 1 for the outermost backtick; nested quasiquotes
+/// increment it and nested unquotes decrement it, so only unquotes at
+/// depth 1 are actually evaluated.
+///
+/// `eval` is the interpreter's own form evaluator, threaded in so this
+/// module doesn't need to depend on the rest of the evaluator's internals.
+pub fn expand_quasiquote<F>(node: &Node, depth: i32, eval: &mut F) -> Result<Atom, Error>
+    where F: FnMut(&Node) -> Result<Atom, Error>
+{
+    match *node {
+        Node::Atom(ref a, _) => Ok(a.clone()),
+        Node::List(ref items, _) => {
+            let mut out: VecDeque<Atom> = VecDeque::new();
+
+            for item in items {
+                match *item {
+                    SyntaxNode::Unquote(ref inner, _) if depth == 1 => {
+                        out.push_back(try!(eval(inner)));
+                    },
+                    SyntaxNode::Splice(ref inner, _) if depth == 1 => {
+                        match try!(eval(inner)) {
+                            Atom::List(spliced) => out.extend(spliced),
+                            _ => return Err(Error::Eval("splice-unquote (~@) must evaluate to a list".to_string()))
+                        }
+                    },
+                    SyntaxNode::Unquote(ref inner, _) => {
+                        out.push_back(try!(expand_quasiquote(inner, depth - 1, eval)));
+                    },
+                    SyntaxNode::Splice(ref inner, _) => {
+                        out.push_back(try!(expand_quasiquote(inner, depth - 1, eval)));
+                    },
+                    SyntaxNode::QuasiQuote(ref inner, _) => {
+                        out.push_back(try!(expand_quasiquote(inner, depth + 1, eval)));
+                    },
+                    SyntaxNode::Quote(ref inner, _) => {
+                        out.push_back(try!(quote_to_atom(inner)));
+                    },
+                    SyntaxNode::Node(ref inner, _) => {
+                        out.push_back(try!(expand_quasiquote(inner, depth, eval)));
+                    },
+                }
+            }
+
+            Ok(Atom::List(out))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parser::tokenize;
+    use number::Number;
+
+    fn quasiquote_of(src: &str) -> Node {
+        match tokenize(src).unwrap() {
+            SyntaxNode::QuasiQuote(node, _) => node,
+            other => panic!("expected a quasiquoted form, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn quotes_plain_elements_verbatim() {
+        let node = quasiquote_of("`(a b 1)");
+        let mut eval = |_: &Node| -> Result<Atom, Error> { panic!("should not evaluate anything") };
+        let result = expand_quasiquote(&node, 1, &mut eval).unwrap();
+
+        assert_eq!(Atom::List(vec![
+            Atom::Symbol("a".to_string()),
+            Atom::Symbol("b".to_string()),
+            Atom::Number(Number::Integer(1)),
+        ].into_iter().collect()), result);
+    }
+
+    #[test]
+    fn evaluates_unquoted_elements() {
+        let node = quasiquote_of("`(a ~b)");
+        let mut eval = |_: &Node| -> Result<Atom, Error> { Ok(Atom::Number(Number::Integer(42))) };
+        let result = expand_quasiquote(&node, 1, &mut eval).unwrap();
+
+        assert_eq!(Atom::List(vec![
+            Atom::Symbol("a".to_string()),
+            Atom::Number(Number::Integer(42)),
+        ].into_iter().collect()), result);
+    }
+
+    #[test]
+    fn splices_list_results_into_the_surrounding_list() {
+        let node = quasiquote_of("`(a ~@b)");
+        let mut eval = |_: &Node| -> Result<Atom, Error> {
+            Ok(Atom::List(vec![Atom::Number(Number::Integer(1)), Atom::Number(Number::Integer(2))].into_iter().collect()))
+        };
+        let result = expand_quasiquote(&node, 1, &mut eval).unwrap();
+
+        assert_eq!(Atom::List(vec![
+            Atom::Symbol("a".to_string()),
+            Atom::Number(Number::Integer(1)),
+            Atom::Number(Number::Integer(2)),
+        ].into_iter().collect()), result);
+    }
+
+    #[test]
+    fn splice_requires_a_list_result() {
+        let node = quasiquote_of("`(a ~@b)");
+        let mut eval = |_: &Node| -> Result<Atom, Error> { Ok(Atom::Number(Number::Integer(1))) };
+
+        assert_eq!(Err(Error::Eval("splice-unquote (~@) must evaluate to a list".to_string())),
+                   expand_quasiquote(&node, 1, &mut eval));
+    }
+
+    #[test]
+    fn nested_quasiquote_defers_its_unquotes() {
+        // the inner `~b` is at unquote-depth 2 relative to the outer backtick,
+        // so it must not be evaluated by this (outermost) expansion.
+        let node = quasiquote_of("`(a `(~b))");
+        let mut eval = |_: &Node| -> Result<Atom, Error> { panic!("depth-2 unquote should not be evaluated here") };
+        let result = expand_quasiquote(&node, 1, &mut eval).unwrap();
+
+        assert_eq!(Atom::List(vec![
+            Atom::Symbol("a".to_string()),
+            Atom::List(vec![Atom::Symbol("b".to_string())].into_iter().collect()),
+        ].into_iter().collect()), result);
+    }
+}
@@ -0,0 +1,27 @@
+//! Optional native-code backend: the request asks for a Cranelift-based
+//! JIT that lowers a compiled opcode stream (see `compiler`/`opcodes`)
+//! through Cranelift instead of walking it with the tree-walking
+//! `vm::VirtualMachine`, exposing something like
+//! `compile_native(&vm, code) -> NativeFn` that the REPL could call
+//! alongside `eval_string` once a form is hot.
+//!
+//! Blocked: `opcodes`, `compiler`, and `vm` don't exist anywhere in this
+//! tree (no commit in this series adds them), and there is still no
+//! Cargo.toml to declare a `jit` feature or the `cranelift-codegen` /
+//! `cranelift-frontend` / `cranelift-jit` / `cranelift-module` crates this
+//! backend would pull in. Without a real `opcodes::OpCode` enum there is
+//! nothing to match on, so the previous draft of this module called into
+//! types that exist nowhere in the tree and could not compile under any
+//! configuration. Rather than ship that, this is left as a documented
+//! blocker until there's a real instruction set to lower:
+//!
+//! - lower each `OpCode` variant to Cranelift IR inside a
+//!   `cranelift_frontend::FunctionBuilder` -- integer/float arithmetic
+//!   maps onto `iadd`/`fadd`-style instructions, conditional jumps become
+//!   IR blocks
+//! - fall back to `vm::VirtualMachine`'s existing interpreter helpers for
+//!   non-numeric `Atom`s and `base_lib` builtins rather than reproducing
+//!   them in IR
+//!
+//! Revisit once `opcodes`, `compiler`, and `vm` land with a real,
+//! enumerable instruction set to lower.
@@ -18,6 +18,8 @@ mod funcs;
 mod compiler;
 mod vm;
 mod base_lib;
+#[cfg(feature = "jit")]
+mod jit;
 
 use errors::Error;
 use std::env::args;
@@ -64,8 +66,17 @@ fn repl() {
                                 lines.clear();
                             }
                             Err(Error::EoF) => {}
-                            Err(e) => {
-                                println!("Error in evaluation: {:?}", e);
+                            Err(Error::Parser(pos, msg)) => {
+                                if let Some(line) = lines.lines().nth(pos.line - 1) {
+                                    println!("{}", line);
+                                    println!("{}^ {}", " ".repeat(pos.column - 1), msg);
+                                } else {
+                                    println!("Parse error at {}: {}", pos, msg);
+                                }
+                                lines.clear();
+                            }
+                            Err(Error::Eval(msg)) => {
+                                println!("Error: {}", msg);
                                 lines.clear();
                             }
                         }
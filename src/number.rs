@@ -0,0 +1,16 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Number::Integer(i) => write!(f, "{}", i),
+            Number::Float(n) => write!(f, "{}", n),
+        }
+    }
+}
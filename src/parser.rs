@@ -5,44 +5,134 @@ use std::iter::*;
 use std::io::prelude::*;
 
 pub fn tokenize(line: &str) -> ParseResult {
-    let mut chars = line.chars().peekable();
-    return read_tokens(&mut chars);
+    let mut scanner = Scanner::new(line);
+    let result = try!(read_tokens(&mut scanner));
+
+    // A complete form was read, but if anything other than whitespace is
+    // left over (e.g. a stray `)` after a balanced list) it would otherwise
+    // be silently dropped on the floor instead of being reported.
+    scanner.skip_whitespace();
+
+    if scanner.peek().is_some() {
+        return Err(Error::Parser(scanner.position(), "unexpected trailing input after a complete form".to_string()))
+    }
+
+    Ok(result)
 }
 
-#[derive(Debug, PartialEq, Clone)]
+/// Each variant carries the `SourcePosition` it started at (the opening
+/// `(` of a list, or the atom's own first char), so passes further down the
+/// pipeline -- eval-time errors, tooling -- can still point back at source
+/// instead of only the parser itself being able to. Equality deliberately
+/// ignores it (see the hand-written `PartialEq` impls below): two nodes
+/// parsed from different places but denoting the same value should still
+/// compare equal, which is what every existing test already assumes.
+#[derive(Debug, Clone)]
 pub enum Node {
-    Atom(Atom),
-    List(Vec<SyntaxNode>)
+    Atom(Atom, SourcePosition),
+    List(Vec<SyntaxNode>, SourcePosition)
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Node) -> bool {
+        use self::Node::*;
+        match (self, other) {
+            (&Atom(ref a, _), &Atom(ref b, _)) => a == b,
+            (&List(ref a, _), &List(ref b, _)) => a == b,
+            _ => false
+        }
+    }
+}
+
+impl Node {
+    pub fn position(&self) -> SourcePosition {
+        match *self {
+            Node::Atom(_, pos) => pos,
+            Node::List(_, pos) => pos,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub enum SyntaxNode {
-    Node(Node),
-    Quote(Node),
-    QuasiQuote(Node),
-    Splice(Node)
+    Node(Node, SourcePosition),
+    Quote(Node, SourcePosition),
+    QuasiQuote(Node, SourcePosition),
+    Unquote(Node, SourcePosition),
+    Splice(Node, SourcePosition)
+}
+
+impl PartialEq for SyntaxNode {
+    fn eq(&self, other: &SyntaxNode) -> bool {
+        use self::SyntaxNode::*;
+        match (self, other) {
+            (&Node(ref a, _), &Node(ref b, _)) => a == b,
+            (&Quote(ref a, _), &Quote(ref b, _)) => a == b,
+            (&QuasiQuote(ref a, _), &QuasiQuote(ref b, _)) => a == b,
+            (&Unquote(ref a, _), &Unquote(ref b, _)) => a == b,
+            (&Splice(ref a, _), &Splice(ref b, _)) => a == b,
+            _ => false
+        }
+    }
+}
+
+impl SyntaxNode {
+    pub fn position(&self) -> SourcePosition {
+        match *self {
+            SyntaxNode::Node(_, pos) => pos,
+            SyntaxNode::Quote(_, pos) => pos,
+            SyntaxNode::QuasiQuote(_, pos) => pos,
+            SyntaxNode::Unquote(_, pos) => pos,
+            SyntaxNode::Splice(_, pos) => pos,
+        }
+    }
 }
 
 pub type ParseResult = Result<SyntaxNode, Error>;
 
 use std::fmt;
 
+/// A 1-indexed line/column together with a 0-indexed absolute char offset,
+/// recorded while scanning so parse errors can point back at the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+    pub index: usize,
+}
+
+impl SourcePosition {
+    fn start() -> SourcePosition {
+        SourcePosition { line: 1, column: 1, index: 0 }
+    }
+}
+
+impl fmt::Display for SourcePosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 impl fmt::Display for SyntaxNode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::SyntaxNode::*;
         match self {
-            &Node(ref node) => {
+            &Node(ref node, _) => {
                 write!(f, "{}", node)
             },
-            &Quote(ref node) => {
+            &Quote(ref node, _) => {
                 try!(write!(f, "'"));
                 write!(f, "{}", node)
             },
-            &QuasiQuote(ref node) => {
+            &QuasiQuote(ref node, _) => {
                 try!(write!(f, "`"));
                 write!(f, "{}", node)
             },
-            &Splice(ref node) => {
+            &Unquote(ref node, _) => {
+                try!(write!(f, "~"));
+                write!(f, "{}", node)
+            },
+            &Splice(ref node, _) => {
                 try!(write!(f, "~@"));
                 write!(f, "{}", node)
             }
@@ -54,10 +144,10 @@ impl fmt::Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Node::*;
         match *self {
-            Atom(ref a) => {
+            Atom(ref a, _) => {
                 write!(f, "{}", a)
             },
-            List(ref list) => {
+            List(ref list, _) => {
                 try!(write!(f, "("));
                 if let Some(first) = list.first() {
                     try!(write!(f, "{}", first));
@@ -85,50 +175,108 @@ enum Token {
     Splice,      // ~@
 }
 
+/// Wraps the raw `Peekable<Chars>` scan so every consumed character advances
+/// a running line/column/index counter, used to position parse errors.
+struct Scanner<'a> {
+    chars: Peekable<Chars<'a>>,
+    pos: SourcePosition,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(s: &'a str) -> Scanner<'a> {
+        Scanner {
+            chars: s.chars().peekable(),
+            pos: SourcePosition::start(),
+        }
+    }
+
+    fn position(&self) -> SourcePosition {
+        self.pos
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.chars.peek()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+
+        if let Some(ch) = c {
+            self.pos.index += 1;
+
+            if ch == '\n' {
+                self.pos.line += 1;
+                self.pos.column = 1;
+            } else {
+                self.pos.column += 1;
+            }
+        }
+
+        c
+    }
+
+    /// Consumes any run of whitespace the scanner is currently sitting on,
+    /// leaving it positioned at the next non-whitespace char (or EOF).
+    fn skip_whitespace(&mut self) {
+        loop {
+            match self.peek() {
+                Some(&c) if c.is_whitespace() => { self.next(); },
+                _ => break
+            }
+        }
+    }
+}
+
 use std::convert::From;
 
 impl Node {
+    // These build a value directly, with no real source to point at, so
+    // they're stamped with `SourcePosition::start()` -- harmless, since
+    // `PartialEq` above ignores position entirely.
     fn symbol(s: &str) -> Node {
-        Node::Atom(Atom::Symbol(s.to_string()))
+        Node::Atom(Atom::Symbol(s.to_string()), SourcePosition::start())
     }
 
     fn string(s: &str) -> Node {
-        Node::Atom(Atom::String(s.to_string()))
+        Node::Atom(Atom::String(s.to_string()), SourcePosition::start())
     }
 
     fn list(v: &[Atom]) -> Node {
         use std::collections::*;
-        Node::Atom(Atom::List(v.iter().map(|x| x.clone()).collect::<VecDeque<Atom>>()))
+        Node::Atom(Atom::List(v.iter().map(|x| x.clone()).collect::<VecDeque<Atom>>()), SourcePosition::start())
     }
 }
 
 impl From<i64> for Node {
     fn from(i: i64) -> Node {
-        Node::Atom(Atom::Number(Number::Integer(i)))
+        Node::Atom(Atom::Number(Number::Integer(i)), SourcePosition::start())
     }
 }
 
 impl From<f64> for Node {
     fn from(f: f64) -> Node {
-        Node::Atom(Atom::Number(Number::Float(f)))
+        Node::Atom(Atom::Number(Number::Float(f)), SourcePosition::start())
     }
 }
 
 impl From<bool> for Node {
     fn from(b: bool) -> Node {
-        Node::Atom(Atom::Boolean(b))
+        Node::Atom(Atom::Boolean(b), SourcePosition::start())
     }
 }
 
-fn read_string(iter: &mut Peekable<Chars>) -> Result<Option<Token>, Error> {
+fn read_string(start: SourcePosition, scanner: &mut Scanner) -> Result<Option<Token>, Error> {
     let mut s = String::new();
     loop {
-        match iter.next() {
+        match scanner.next() {
             Some('\\') => {
-                if let Some(escaped) = iter.next() {
+                if let Some(escaped) = scanner.next() {
                     s.push(escaped);
                 } else {
-                    return Err(Error::Parser)
+                    // Point at the opening quote, same as running out of
+                    // input mid-string below, rather than wherever the
+                    // dangling backslash happened to be.
+                    return Err(Error::Parser(start, "unterminated string".to_string()))
                 }
             },
             Some('"') => {
@@ -137,23 +285,58 @@ fn read_string(iter: &mut Peekable<Chars>) -> Result<Option<Token>, Error> {
                 return Ok(Some(t))
             },
             Some(c) => s.push(c),
-            None => return Err(Error::Parser)
+            None => return Err(Error::Parser(start, "unterminated string".to_string()))
+        }
+    }
+}
+
+fn read_char(start: SourcePosition, scanner: &mut Scanner) -> Result<Option<Token>, Error> {
+    let first = match scanner.next() {
+        Some(c) => c,
+        None => return Err(Error::Parser(start, "unterminated character literal".to_string()))
+    };
+
+    if !first.is_alphabetic() {
+        return Ok(Some(Token::Atom(Atom::Char(first))))
+    }
+
+    let mut name = String::new();
+    name.push(first);
+
+    loop {
+        match scanner.peek() {
+            Some(&c) if c.is_alphabetic() => {
+                name.push(c);
+                scanner.next();
+            },
+            _ => break
         }
     }
+
+    if name.len() == 1 {
+        return Ok(Some(Token::Atom(Atom::Char(first))))
+    }
+
+    match &name[..] {
+        "space" => Ok(Some(Token::Atom(Atom::Char(' ')))),
+        "newline" => Ok(Some(Token::Atom(Atom::Char('\n')))),
+        "tab" => Ok(Some(Token::Atom(Atom::Char('\t')))),
+        _ => Err(Error::Parser(start, format!("unknown character name #\\{}", name)))
+    }
 }
 
-fn read_atom(c: char, iter: &mut Peekable<Chars>) -> Result<Option<Token>,Error> {
+fn read_atom(c: char, scanner: &mut Scanner) -> Result<Option<Token>, Error> {
     let mut s = String::new();
     s.push(c);
     loop {
-        match iter.peek() {
+        match scanner.peek() {
             Some(&')') =>  {
                 return Ok(Some(Token::Atom(Atom::parse(&s))))
             },
             _ => ()
         }
 
-        if let Some(c) = iter.next() {
+        if let Some(c) = scanner.next() {
             if !c.is_whitespace() {
                 s.push(c);
             } else {
@@ -165,17 +348,38 @@ fn read_atom(c: char, iter: &mut Peekable<Chars>) -> Result<Option<Token>,Error>
     }
 }
 
-fn next(iter: &mut Peekable<Chars>) -> Result<Option<Token>, Error> {
+fn next(scanner: &mut Scanner) -> Result<Option<(Token, SourcePosition)>, Error> {
     loop {
-        if let Some(c) = iter.next() {
+        let start = scanner.position();
+
+        if let Some(c) = scanner.next() {
             match c {
-                '(' => return Ok(Some(Token::Open)),
-                '"' => return read_string(iter),
-                ')' => return Ok(Some(Token::Close)),
-                '\'' => return Ok(Some(Token::Quote)),
+                '(' => return Ok(Some((Token::Open, start))),
+                '"' => return Ok(try!(read_string(start, scanner)).map(|t| (t, start))),
+                ')' => return Ok(Some((Token::Close, start))),
+                '#' => {
+                    match scanner.peek() {
+                        Some(&'\\') => {
+                            scanner.next();
+                            return Ok(try!(read_char(start, scanner)).map(|t| (t, start)))
+                        },
+                        _ => return Ok(try!(read_atom(c, scanner)).map(|t| (t, start)))
+                    }
+                },
+                '\'' => return Ok(Some((Token::Quote, start))),
+                '`' => return Ok(Some((Token::SyntaxQuote, start))),
+                '~' => {
+                    match scanner.peek() {
+                        Some(&'@') => {
+                            scanner.next();
+                            return Ok(Some((Token::Splice, start)))
+                        },
+                        _ => return Ok(Some((Token::Unquote, start)))
+                    }
+                },
                 ';' =>  {
                     loop {
-                        match iter.next() {
+                        match scanner.next() {
                             Some('\n') => break,
                             Some(_) => (),
                             None => return Ok(None)
@@ -183,7 +387,7 @@ fn next(iter: &mut Peekable<Chars>) -> Result<Option<Token>, Error> {
                     }
                 },
                 _ if c.is_whitespace() => (),
-                _ => return read_atom(c, iter)
+                _ => return Ok(try!(read_atom(c, scanner)).map(|t| (t, start)))
             }
         } else {
             return Ok(None)
@@ -191,61 +395,330 @@ fn next(iter: &mut Peekable<Chars>) -> Result<Option<Token>, Error> {
     }
 }
 
-fn read_tokens(chars: &mut Peekable<Chars>) -> ParseResult {
-    match next(chars) {
-        Ok(Some(Token::Open)) => {
+fn read_tokens(scanner: &mut Scanner) -> ParseResult {
+    match try!(next(scanner)) {
+        Some((Token::Open, start)) => {
             let mut node: Vec<SyntaxNode> = vec![];
 
             loop {
-                match chars.peek() {
+                // Whitespace between the last element and the closing
+                // paren would otherwise look like the start of another
+                // element once it's peeked at directly below.
+                scanner.skip_whitespace();
+
+                match scanner.peek() {
                     Some(&')') => {
-                        chars.next();
+                        scanner.next();
                         break
                     },
                     Some(_) => {
-                        let token = try!(read_tokens(chars));
+                        let token = try!(read_tokens(scanner));
                         node.push(token);
                     },
                     _ => {
-                        return Err(Error::Parser)
+                        return Err(Error::Parser(scanner.position(), "EOF inside list".to_string()))
                     }
                 }
             }
 
-            return Ok(SyntaxNode::Node(Node::List(node)))
+            return Ok(SyntaxNode::Node(Node::List(node, start), start))
         },
-        Ok(Some(Token::Close)) => {
-            return Err(Error::Parser)
+        Some((Token::Close, pos)) => {
+            return Err(Error::Parser(pos, "unexpected ')'".to_string()))
         },
-        Ok(Some(Token::Quote)) => {
-            if let SyntaxNode::Node(node) = try!(read_tokens(chars)) {
-                return Ok(SyntaxNode::Quote(node))
+        Some((Token::Quote, pos)) => {
+            if let SyntaxNode::Node(node, _) = try!(read_tokens(scanner)) {
+                return Ok(SyntaxNode::Quote(node, pos))
             }
-            ()
-        }
-        Ok(Some(Token::Atom(x))) => return Ok(SyntaxNode::Node(Node::Atom(x))),
-        _ => ()
+            return Err(Error::Parser(pos, "quote cannot directly wrap another quoted or unquoted form".to_string()))
+        },
+        Some((Token::SyntaxQuote, pos)) => {
+            if let SyntaxNode::Node(node, _) = try!(read_tokens(scanner)) {
+                return Ok(SyntaxNode::QuasiQuote(node, pos))
+            }
+            return Err(Error::Parser(pos, "quasiquote cannot directly wrap another quoted or unquoted form".to_string()))
+        },
+        Some((Token::Unquote, pos)) => {
+            if let SyntaxNode::Node(node, _) = try!(read_tokens(scanner)) {
+                return Ok(SyntaxNode::Unquote(node, pos))
+            }
+            return Err(Error::Parser(pos, "unquote cannot directly wrap another quoted or unquoted form".to_string()))
+        },
+        Some((Token::Splice, pos)) => {
+            if let SyntaxNode::Node(node, _) = try!(read_tokens(scanner)) {
+                return Ok(SyntaxNode::Splice(node, pos))
+            }
+            return Err(Error::Parser(pos, "splice-unquote cannot directly wrap another quoted or unquoted form".to_string()))
+        },
+        Some((Token::Atom(x), pos)) => return Ok(SyntaxNode::Node(Node::Atom(x, pos), pos)),
+        None => return Err(Error::EoF)
     }
-    Err(Error::Parser)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn node_of(r: ParseResult) -> Node {
+        match r.unwrap() {
+            SyntaxNode::Node(n, _) => n,
+            other => panic!("expected a bare node, got {:?}", other)
+        }
+    }
+
     #[test]
     fn naked_atoms() {
-        assert_eq!(Node::from(0), tokenize("0").unwrap());
-        assert_eq!(Node::from(512), tokenize("512").unwrap());
-        assert_eq!(Node::from(-512), tokenize("-512").unwrap());
-        assert_eq!(Node::from(5.0f64), tokenize("5.0").unwrap());
-        assert_eq!(Node::string("foo bar"), tokenize("\"foo bar\"").unwrap());
-        assert_eq!(Node::symbol("foo"), tokenize("foo").unwrap());
+        assert_eq!(Node::from(0), node_of(tokenize("0")));
+        assert_eq!(Node::from(512), node_of(tokenize("512")));
+        assert_eq!(Node::from(-512), node_of(tokenize("-512")));
+        assert_eq!(Node::from(5.0f64), node_of(tokenize("5.0")));
+        assert_eq!(Node::string("foo bar"), node_of(tokenize("\"foo bar\"")));
+        assert_eq!(Node::symbol("foo"), node_of(tokenize("foo")));
     }
 
     #[test]
     fn string_escaping() {
-        assert_eq!(Node::string("foo'bar"), tokenize("\"foo\\'bar\"").unwrap());
-        assert_eq!(Node::string("foo\"bar"), tokenize("\"foo\\\"bar\"").unwrap());
+        assert_eq!(Node::string("foo'bar"), node_of(tokenize("\"foo\\'bar\"")));
+        assert_eq!(Node::string("foo\"bar"), node_of(tokenize("\"foo\\\"bar\"")));
+    }
+
+    #[test]
+    fn char_literals() {
+        assert_eq!(Node::Atom(Atom::Char('a'), SourcePosition::start()), node_of(tokenize("#\\a")));
+        assert_eq!(Node::Atom(Atom::Char(' '), SourcePosition::start()), node_of(tokenize("#\\space")));
+        assert_eq!(Node::Atom(Atom::Char('\n'), SourcePosition::start()), node_of(tokenize("#\\newline")));
+        assert_eq!(Node::Atom(Atom::Char('\t'), SourcePosition::start()), node_of(tokenize("#\\tab")));
+    }
+
+    #[test]
+    fn dangling_char_literal_at_eof_is_an_error() {
+        match tokenize("#\\") {
+            Err(Error::Parser(_, _)) => (),
+            other => panic!("expected a positioned parser error, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn unterminated_string_reports_the_opening_quote() {
+        // Whether the string runs out of input mid-character or right after
+        // a dangling trailing backslash, the error should point at the same
+        // place: the opening `"`, not wherever the scanner happened to stop.
+        for src in &["\"foo", "\"foo\\"] {
+            match tokenize(src) {
+                Err(Error::Parser(pos, _)) => {
+                    assert_eq!(1, pos.line);
+                    assert_eq!(1, pos.column);
+                },
+                other => panic!("expected a positioned parser error, got {:?}", other)
+            }
+        }
+    }
+
+    #[test]
+    fn reports_position_of_unexpected_close_paren() {
+        match tokenize(")") {
+            Err(Error::Parser(pos, _)) => {
+                assert_eq!(1, pos.line);
+                assert_eq!(1, pos.column);
+            },
+            other => panic!("expected a positioned parser error, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn reports_position_of_trailing_garbage_after_a_complete_form() {
+        // A fully-formed list followed by a stray ')' used to be silently
+        // accepted -- `tokenize` only read the first complete form and
+        // never looked at what, if anything, came after it.
+        match tokenize("(foo))") {
+            Err(Error::Parser(pos, _)) => {
+                assert_eq!(1, pos.line);
+                assert_eq!(6, pos.column);
+            },
+            other => panic!("expected a positioned parser error, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn quasiquote_unquote_and_splice() {
+        match tokenize("`(a ~b ~@c)") {
+            Ok(SyntaxNode::QuasiQuote(Node::List(ref items, _), _)) => {
+                assert_eq!(Node::symbol("a"), node_of(Ok(items[0].clone())));
+                assert_eq!(SyntaxNode::Unquote(Node::symbol("b"), SourcePosition::start()), items[1]);
+                assert_eq!(SyntaxNode::Splice(Node::symbol("c"), SourcePosition::start()), items[2]);
+            },
+            other => panic!("expected a quasiquoted list, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn reports_position_across_lines() {
+        match tokenize("(foo\n bar\n  )") {
+            Ok(_) => (),
+            other => panic!("expected a well-formed list, got {:?}", other)
+        }
+
+        match tokenize("(foo\n bar") {
+            Err(Error::Parser(pos, _)) => {
+                assert_eq!(2, pos.line);
+            },
+            other => panic!("expected a positioned parser error, got {:?}", other)
+        }
     }
-}
\ No newline at end of file
+}
+
+/// Property-test and corpus-replay harness for the reader. `SyntaxNode` and
+/// `Node` both implement `Display`, so a round trip of render-then-retokenize
+/// should hand back the tree it started from; running that over random
+/// trees (quoted, quasiquoted, some preceded by a `;` comment line) catches
+/// reader regressions (string escaping, comment handling, nested quotes)
+/// that the hand-written `tests` above don't happen to hit. The second half
+/// replays a fixed corpus of `.lisp` files and asserts each one either
+/// parses or fails with a positioned `Error::Parser`, never panics. Also
+/// wired to `#![feature(test)]` so tokenizing a large input can be
+/// benchmarked alongside it.
+#[cfg(test)]
+mod conformance {
+    use super::*;
+    use std::fs::File;
+    use std::io::prelude::*;
+    use std::path::Path;
+    use test::Bencher;
+
+    /// A tiny xorshift64 generator, used only so this harness doesn't need
+    /// an external `rand` dependency.
+    struct Rng(u64);
+
+    impl Rng {
+        fn new(seed: u64) -> Rng {
+            Rng(seed | 1)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut x = self.0;
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            self.0 = x;
+            x
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next_u64() as usize) % n
+        }
+    }
+
+    fn random_string(rng: &mut Rng) -> String {
+        let chars = ['a', 'b', ' ', '"', '\\'];
+        let len = rng.below(6);
+        (0..len).map(|_| chars[rng.below(chars.len())]).collect()
+    }
+
+    fn random_char(rng: &mut Rng) -> char {
+        // Mixes plain chars in with the three named escapes (`#\space`,
+        // `#\newline`, `#\tab`) that `read_char`/`Display` special-case.
+        let chars = ['a', 'b', ' ', '\n', '\t'];
+        chars[rng.below(chars.len())]
+    }
+
+    fn random_node(rng: &mut Rng, depth: usize) -> Node {
+        if depth == 0 || rng.below(3) == 0 {
+            match rng.below(5) {
+                0 => Node::from(rng.below(1000) as i64),
+                1 => Node::symbol(&format!("sym{}", rng.below(26))),
+                2 => Node::string(&random_string(rng)),
+                // Fuzz-generated, not parsed from real source, so there's
+                // no span to give it -- PartialEq ignores position anyway.
+                3 => Node::Atom(Atom::Char(random_char(rng)), SourcePosition::start()),
+                _ => Node::from(rng.below(2) == 0),
+            }
+        } else {
+            let len = rng.below(4);
+            let items = (0..len).map(|_| random_syntax_node(rng, depth - 1)).collect();
+            Node::List(items, SourcePosition::start())
+        }
+    }
+
+    /// Wraps a random node in `'`/`` ` ``/`~`/`~@` (each about a fifth of
+    /// the time, so only one node in five is left bare), so quoted and
+    /// quasiquoted forms show up both at the top level
+    /// and nested inside lists.
+    fn random_syntax_node(rng: &mut Rng, depth: usize) -> SyntaxNode {
+        let node = random_node(rng, depth);
+        let pos = SourcePosition::start();
+
+        match rng.below(5) {
+            0 => SyntaxNode::Quote(node, pos),
+            1 => SyntaxNode::QuasiQuote(node, pos),
+            2 => SyntaxNode::Unquote(node, pos),
+            3 => SyntaxNode::Splice(node, pos),
+            _ => SyntaxNode::Node(node, pos),
+        }
+    }
+
+    /// Prepends a `;`-comment line ahead of `src` about half the time, so the
+    /// reader actually has to skip over it before reaching the real form.
+    /// (A trailing comment isn't used: after the form `tokenize` skips only
+    /// whitespace and rejects anything else, `;` comments included.)
+    fn with_comment_noise(rng: &mut Rng, src: &str) -> String {
+        if rng.below(2) == 0 {
+            format!("; fuzz comment {}\n{}", rng.below(1000), src)
+        } else {
+            src.to_string()
+        }
+    }
+
+    #[test]
+    fn round_trips_random_syntax_trees_through_display_and_tokenize() {
+        let mut rng = Rng::new(0xdeadbeef);
+
+        for i in 0..200 {
+            let node = random_syntax_node(&mut rng, 4);
+            let rendered = with_comment_noise(&mut rng, &format!("{}", node));
+            let reparsed = tokenize(&rendered).unwrap();
+            assert_eq!(node, reparsed, "round-trip #{} failed for {:?}", i, rendered);
+        }
+    }
+
+    #[test]
+    fn replays_corpus_without_panicking() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+
+        for entry in ::std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.extension().map_or(false, |e| e == "lisp") {
+                let mut file = File::open(&path).unwrap();
+                let mut src = String::new();
+                file.read_to_string(&mut src).unwrap();
+
+                // `tokenize` reads one top-level form and rejects anything
+                // but whitespace after it, so replay the scanner directly
+                // and keep reading forms until the file is exhausted --
+                // otherwise a multi-form corpus file would fail on its
+                // second form instead of having every form tested.
+                let mut scanner = Scanner::new(&src);
+
+                loop {
+                    match read_tokens(&mut scanner) {
+                        Ok(_) => continue,
+                        Err(Error::EoF) => break,
+                        Err(Error::Parser(_, _)) => break,
+                        Err(e) => panic!("{:?} produced a non-positioned error: {:?}", path, e)
+                    }
+                }
+            }
+        }
+    }
+
+    #[bench]
+    fn bench_tokenize_large_input(b: &mut Bencher) {
+        let mut src = String::from("(");
+        for i in 0..2000 {
+            src.push_str(&format!("(add {} {}) ", i, i + 1));
+        }
+        src.push(')');
+
+        b.iter(|| tokenize(&src).unwrap());
+    }
+}